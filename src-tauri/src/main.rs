@@ -1,66 +1,377 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::api::process::{Command, CommandChild, CommandEvent};
 use tauri::Manager;
-use std::process::{Command, Stdio};
-use std::path::PathBuf;
+
+mod microphone;
+use microphone::MicrophonePermissionStatus;
+
+/// Port the embedded backend listens on by default; overridable with the
+/// `VERBA_BACKEND_PORT` env var until a config file lands.
+const DEFAULT_BACKEND_PORT: u16 = 8008;
+
+/// How many times the supervisor will respawn a crashed backend before
+/// giving up and reporting `backend-failed`.
+const MAX_BACKEND_RETRIES: u32 = 5;
+
+/// Base delay for the supervisor's exponential backoff between respawns.
+const RETRY_BACKOFF_BASE_MS: u64 = 500;
+
+/// Backend lifecycle status, tracked as managed state so any command (and the
+/// frontend's splash/retry gate) can ask "is the backend usable yet?".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BackendStatus {
+    Starting,
+    Ready,
+    Unhealthy,
+    Crashed,
+}
+
+/// A spawned backend process together with the flag its log-reader task
+/// flips once it observes `CommandEvent::Terminated` (`shutdown_backend`
+/// polls this instead of blind-sleeping to its timeout) and the generation
+/// that spawn belongs to. The generation lets `on_backend_terminated` tell
+/// "this termination is for the process still in `ctx.child`" apart from
+/// "this is a stale event for a process we've already replaced or
+/// deliberately killed" — a shared bool can't express that once a restart
+/// races a crash of the process it's replacing.
+struct SpawnedBackend {
+    child: CommandChild,
+    exited: Arc<AtomicBool>,
+    generation: u64,
+}
+
+/// Single source of truth for the embedded backend's lifecycle: the spawned
+/// process handle, which port it's reachable on, and its last known health.
+/// Constructed once in `main` and shared via `.manage()` so commands take
+/// `State<BackendContext>` instead of threading several mutexes around.
+struct BackendContext {
+    child: Mutex<Option<SpawnedBackend>>,
+    port: u16,
+    status: Mutex<BackendStatus>,
+    /// Incremented on every spawn attempt; see `SpawnedBackend::generation`.
+    next_generation: Mutex<u64>,
+    retry_count: Mutex<u32>,
+}
+
+impl BackendContext {
+    fn new() -> Self {
+        let port = std::env::var("VERBA_BACKEND_PORT")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_BACKEND_PORT);
+
+        Self {
+            child: Mutex::new(None),
+            port,
+            status: Mutex::new(BackendStatus::Starting),
+            next_generation: Mutex::new(0),
+            retry_count: Mutex::new(0),
+        }
+    }
+}
+
+/// Payload emitted on `backend-log` for each line the backend writes to
+/// stdout/stderr, so the frontend can render a live log view.
+#[derive(Clone, serde::Serialize)]
+struct BackendLogPayload {
+    stream: &'static str,
+    line: String,
+}
 
 #[tauri::command]
-fn start_backend() -> Result<String, String> {
-    // Get resource directory
-    let resource_dir = tauri::utils::platform::resource_dir(&tauri::Env::default())
-        .ok_or("Failed to get resource directory")?;
-    
-    // Path to embedded Python and main.py
-    let python_path = resource_dir.join("backend").join("python").join("python");
-    let backend_path = resource_dir.join("backend").join("main.py");
-    
-    // Try different Python executables
-    let python_executables = vec![
-        python_path,
-        PathBuf::from("python3"),
-        PathBuf::from("python"),
-    ];
-    
-    for python_exe in python_executables {
-        match Command::new(&python_exe)
-            .arg(&backend_path)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-        {
-            Ok(_) => return Ok(format!("Backend started successfully with {:?}", python_exe)),
-            Err(_) => continue,
+fn start_backend(app: tauri::AppHandle, ctx: tauri::State<BackendContext>) -> Result<String, String> {
+    let generation = {
+        let mut next = ctx.next_generation.lock().unwrap();
+        *next += 1;
+        *next
+    };
+
+    // The embedded Python backend is bundled as the `main` sidecar binary
+    // (see `externalBin` in tauri.conf.json); `new_sidecar` resolves its
+    // platform-specific path for us instead of us guessing at one.
+    let (mut rx, child) = Command::new_sidecar("main")
+        .map_err(|err| err.to_string())?
+        .args(["--port", &ctx.port.to_string()])
+        .spawn()
+        .map_err(|err| err.to_string())?;
+
+    let exited = Arc::new(AtomicBool::new(false));
+    *ctx.child.lock().unwrap() = Some(SpawnedBackend {
+        child,
+        exited: exited.clone(),
+        generation,
+    });
+
+    let log_app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut exit_code = None;
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => {
+                    let _ = log_app.emit_all(
+                        "backend-log",
+                        BackendLogPayload { stream: "stdout", line },
+                    );
+                }
+                CommandEvent::Stderr(line) => {
+                    let _ = log_app.emit_all(
+                        "backend-log",
+                        BackendLogPayload { stream: "stderr", line },
+                    );
+                }
+                CommandEvent::Terminated(payload) => {
+                    exited.store(true, Ordering::Relaxed);
+                    exit_code = payload.code;
+                    let _ = log_app.emit_all(
+                        "backend-log",
+                        BackendLogPayload {
+                            stream: "terminated",
+                            line: format!("{:?}", payload.code),
+                        },
+                    );
+                    break;
+                }
+                _ => {}
+            }
+        }
+        on_backend_terminated(log_app, generation, exit_code).await;
+    });
+
+    Ok("Backend started successfully".to_string())
+}
+
+/// What to do about a single crash observation, decided purely from the
+/// retry counter so the policy can be tested without spawning a process.
+#[derive(Debug, PartialEq, Eq)]
+enum CrashDecision {
+    Retry { attempt: u32, backoff_ms: u64 },
+    GiveUp { attempts: u32 },
+}
+
+/// Bumps `retry_count` and decides whether the supervisor should respawn
+/// (with exponential backoff) or give up and report `backend-failed`.
+fn record_crash(retry_count: &Mutex<u32>) -> CrashDecision {
+    let attempt = {
+        let mut retries = retry_count.lock().unwrap();
+        *retries += 1;
+        *retries
+    };
+
+    if attempt > MAX_BACKEND_RETRIES {
+        CrashDecision::GiveUp { attempts: attempt }
+    } else {
+        CrashDecision::Retry {
+            attempt,
+            backoff_ms: RETRY_BACKOFF_BASE_MS * 2u64.pow(attempt - 1),
+        }
+    }
+}
+
+/// Runs whenever the backend's sidecar channel closes. If `generation` no
+/// longer matches the process currently in `ctx.child` — because
+/// `shutdown_backend` already took it out for a deliberate stop/restart, or a
+/// later spawn has replaced it — this is a stale event for a process we
+/// already know about, not a crash. A clean `code == 0` exit is logged but
+/// not treated as a crash either; only a missing or non-zero code hands off
+/// to the supervisor's respawn-with-backoff policy.
+async fn on_backend_terminated(app: tauri::AppHandle, generation: u64, exit_code: Option<i32>) {
+    let ctx = app.state::<BackendContext>();
+    let is_current = matches!(
+        &*ctx.child.lock().unwrap(),
+        Some(spawned) if spawned.generation == generation
+    );
+    if !is_current || exit_code == Some(0) {
+        return;
+    }
+
+    *ctx.status.lock().unwrap() = BackendStatus::Crashed;
+    let _ = app.emit_all("backend-crashed", BackendStatus::Crashed);
+
+    match record_crash(&ctx.retry_count) {
+        CrashDecision::GiveUp { attempts } => {
+            let _ = app.emit_all("backend-failed", attempts);
+        }
+        CrashDecision::Retry { backoff_ms, .. } => {
+            tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+            if start_backend(app.clone(), ctx.clone()).is_ok() {
+                let _ = wait_for_backend(app.clone(), ctx, 15_000).await;
+            }
         }
     }
-    
-    Err("Failed to start backend with any Python executable".to_string())
+}
+
+/// Gives the backend process a chance to exit on its own before forcibly
+/// killing it, so we don't leave a zombie python around when the app quits.
+fn shutdown_backend(ctx: &BackendContext, timeout: Duration) {
+    let spawned = ctx.child.lock().unwrap().take();
+    let Some(spawned) = spawned else {
+        return;
+    };
+
+    // Poll the exited flag so a backend that's already gone doesn't stall us
+    // for the whole timeout; only reach for `kill()` if it's still lingering.
+    let deadline = Instant::now() + timeout;
+    while !spawned.exited.load(Ordering::Relaxed) && Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    if !spawned.exited.load(Ordering::Relaxed) {
+        let _ = spawned.child.kill();
+    }
+}
+
+/// Manually restarts the backend: stops any running instance, resets the
+/// supervisor's retry counter, and respawns from a clean slate.
+///
+/// `shutdown_backend` blocks the calling thread while it waits for the old
+/// process to exit, so it runs on a `spawn_blocking` thread rather than the
+/// async executor thread this command is polled on — otherwise it would
+/// stall other concurrent commands (health polling, mic permission checks)
+/// sharing that worker.
+#[tauri::command]
+async fn restart_backend(app: tauri::AppHandle) -> Result<String, String> {
+    let shutdown_app = app.clone();
+    tokio::task::spawn_blocking(move || {
+        let ctx = shutdown_app.state::<BackendContext>();
+        shutdown_backend(&ctx, Duration::from_secs(5));
+    })
+    .await
+    .map_err(|err| err.to_string())?;
+
+    let ctx = app.state::<BackendContext>();
+    *ctx.retry_count.lock().unwrap() = 0;
+    *ctx.status.lock().unwrap() = BackendStatus::Starting;
+
+    let result = start_backend(app.clone(), ctx.clone())?;
+    wait_for_backend(app.clone(), ctx, 15_000).await?;
+    Ok(result)
 }
 
 #[tauri::command]
-async fn check_microphone_permission() -> Result<bool, String> {
-    // This would check microphone permissions on different platforms
-    Ok(true)
+async fn check_microphone_permission() -> Result<MicrophonePermissionStatus, String> {
+    Ok(microphone::check_status())
 }
 
-#[tauri::command] 
-async def get_app_version() -> String {
+#[tauri::command]
+async fn request_microphone_permission() -> Result<MicrophonePermissionStatus, String> {
+    Ok(microphone::request().await)
+}
+
+#[tauri::command]
+async fn get_app_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+#[tauri::command]
+async fn get_backend_status(ctx: tauri::State<'_, BackendContext>) -> Result<BackendStatus, String> {
+    Ok(*ctx.status.lock().unwrap())
+}
+
+/// Polls the backend's port until it accepts a TCP connection or `timeout_ms`
+/// elapses, emitting `backend-ready`/`backend-unhealthy` to all windows and
+/// updating the managed status so other commands can report it too.
+#[tauri::command]
+async fn wait_for_backend(
+    app: tauri::AppHandle,
+    ctx: tauri::State<'_, BackendContext>,
+    timeout_ms: u64,
+) -> Result<BackendStatus, String> {
+    let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+    let status = loop {
+        if TcpStream::connect(("127.0.0.1", ctx.port)).is_ok() {
+            break BackendStatus::Ready;
+        }
+        if Instant::now() >= deadline {
+            break BackendStatus::Unhealthy;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    };
+
+    *ctx.status.lock().unwrap() = status;
+    if status == BackendStatus::Ready {
+        *ctx.retry_count.lock().unwrap() = 0;
+    }
+    let event = match status {
+        BackendStatus::Ready => "backend-ready",
+        BackendStatus::Crashed => "backend-crashed",
+        _ => "backend-unhealthy",
+    };
+    let _ = app.emit_all(event, status);
+
+    Ok(status)
+}
+
 fn main() {
+    let backend_context = BackendContext::new();
+
     tauri::Builder::default()
+        .manage(backend_context)
         .invoke_handler(tauri::generate_handler![
             start_backend,
+            restart_backend,
             check_microphone_permission,
-            get_app_version
+            request_microphone_permission,
+            get_app_version,
+            get_backend_status,
+            wait_for_backend
         ])
         .setup(|app| {
-            // Start backend server on app startup
-            tauri::async_runtime::spawn(async {
-                let _ = start_backend();
+            // Start backend server on app startup, then poll until it's
+            // actually accepting connections before telling the frontend.
+            let app_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                let ctx = app_handle.state::<BackendContext>();
+                let start_result = start_backend(app_handle.clone(), ctx.clone());
+                if start_result.is_err() {
+                    *ctx.status.lock().unwrap() = BackendStatus::Crashed;
+                    let _ = app_handle.emit_all("backend-crashed", BackendStatus::Crashed);
+                    return;
+                }
+                let _ = wait_for_backend(app_handle.clone(), ctx, 15_000).await;
             });
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
-}
\ No newline at end of file
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(move |app_handle, event| {
+            if let tauri::RunEvent::ExitRequested { .. } | tauri::RunEvent::Exit = event {
+                shutdown_backend(&app_handle.state::<BackendContext>(), Duration::from_secs(5));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulates a dummy sidecar that exits immediately every time: each
+    /// crash should bump the retry counter with growing backoff until
+    /// `MAX_BACKEND_RETRIES` is exhausted, then give up for good.
+    #[test]
+    fn early_exiting_sidecar_retries_then_gives_up() {
+        let retry_count = Mutex::new(0);
+
+        for expected_attempt in 1..=MAX_BACKEND_RETRIES {
+            match record_crash(&retry_count) {
+                CrashDecision::Retry { attempt, backoff_ms } => {
+                    assert_eq!(attempt, expected_attempt);
+                    assert_eq!(
+                        backoff_ms,
+                        RETRY_BACKOFF_BASE_MS * 2u64.pow(expected_attempt - 1)
+                    );
+                }
+                decision => panic!("expected a retry on attempt {expected_attempt}, got {decision:?}"),
+            }
+        }
+
+        match record_crash(&retry_count) {
+            CrashDecision::GiveUp { attempts } => assert_eq!(attempts, MAX_BACKEND_RETRIES + 1),
+            decision => panic!("expected give-up after exhausting retries, got {decision:?}"),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use serde::Serialize;
+
+/// Microphone authorization state, mirrored across platforms so the frontend
+/// can distinguish "never asked" from "explicitly denied, go to settings".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MicrophonePermissionStatus {
+    Authorized,
+    Denied,
+    NotDetermined,
+    Unknown,
+}
+
+/// Returns the current microphone permission state without prompting the user.
+pub fn check_status() -> MicrophonePermissionStatus {
+    platform::check_status()
+}
+
+/// Triggers the OS permission prompt when the state is `NotDetermined`, then
+/// returns the resulting status. A no-op on platforms without a prompt.
+pub async fn request() -> MicrophonePermissionStatus {
+    platform::request().await
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::MicrophonePermissionStatus;
+    use objc::runtime::Object;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    // AVAuthorizationStatus raw values, see AVCaptureDevice.h.
+    const AUTHORIZED: i64 = 3;
+    const DENIED: i64 = 2;
+    const NOT_DETERMINED: i64 = 0;
+
+    fn av_media_type_audio() -> *mut Object {
+        unsafe { msg_send![class!(NSString), stringWithUTF8String: c"soun".as_ptr()] }
+    }
+
+    pub fn check_status() -> MicrophonePermissionStatus {
+        unsafe {
+            let media_type = av_media_type_audio();
+            let status: i64 = msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: media_type];
+            match status {
+                AUTHORIZED => MicrophonePermissionStatus::Authorized,
+                DENIED => MicrophonePermissionStatus::Denied,
+                NOT_DETERMINED => MicrophonePermissionStatus::NotDetermined,
+                _ => MicrophonePermissionStatus::Denied,
+            }
+        }
+    }
+
+    pub async fn request() -> MicrophonePermissionStatus {
+        if check_status() != MicrophonePermissionStatus::NotDetermined {
+            return check_status();
+        }
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let tx = std::sync::Mutex::new(Some(tx));
+        unsafe {
+            let media_type = av_media_type_audio();
+            let handler = block::ConcreteBlock::new(move |granted: bool| {
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(granted);
+                }
+            });
+            let handler = handler.copy();
+            let _: () = msg_send![class!(AVCaptureDevice), requestAccessForMediaType: media_type completionHandler: &*handler];
+        }
+
+        match rx.await {
+            Ok(true) => MicrophonePermissionStatus::Authorized,
+            Ok(false) => MicrophonePermissionStatus::Denied,
+            Err(_) => MicrophonePermissionStatus::Unknown,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::MicrophonePermissionStatus;
+    use windows::Media::Capture::MediaCapture;
+    use windows::Media::Capture::MediaCaptureInitializationSettings;
+    use windows::Security::Authorization::AppCapabilityAccess::{
+        AppCapability, AppCapabilityAccessStatus,
+    };
+
+    pub fn check_status() -> MicrophonePermissionStatus {
+        // `AppCapability::CheckAccess` is a non-invasive query against the
+        // privacy setting — unlike initializing a `MediaCapture` session,
+        // it never raises the consent prompt itself.
+        let Ok(capability) = AppCapability::Create(&"microphone".into()) else {
+            return MicrophonePermissionStatus::Unknown;
+        };
+
+        match capability.CheckAccess() {
+            Ok(AppCapabilityAccessStatus::Allowed) => MicrophonePermissionStatus::Authorized,
+            Ok(AppCapabilityAccessStatus::DeniedBySystem)
+            | Ok(AppCapabilityAccessStatus::DeniedByUser) => MicrophonePermissionStatus::Denied,
+            Ok(AppCapabilityAccessStatus::UserPromptRequired) => {
+                MicrophonePermissionStatus::NotDetermined
+            }
+            _ => MicrophonePermissionStatus::Unknown,
+        }
+    }
+
+    pub async fn request() -> MicrophonePermissionStatus {
+        if check_status() != MicrophonePermissionStatus::NotDetermined {
+            return check_status();
+        }
+
+        // Initializing a capture session is what actually raises the
+        // Windows consent prompt, so we only reach for it here, never from
+        // `check_status`.
+        let capture = match MediaCapture::new() {
+            Ok(capture) => capture,
+            Err(_) => return MicrophonePermissionStatus::Unknown,
+        };
+        let settings = match MediaCaptureInitializationSettings::new() {
+            Ok(settings) => settings,
+            Err(_) => return MicrophonePermissionStatus::Unknown,
+        };
+        let _ = settings.SetStreamingCaptureMode(windows::Media::Capture::StreamingCaptureMode::Audio);
+
+        match capture.InitializeWithSettingsAsync(&settings).and_then(|op| op.get()) {
+            Ok(_) => MicrophonePermissionStatus::Authorized,
+            Err(err) if err.code().0 as u32 == 0x8007_0005 => MicrophonePermissionStatus::Denied,
+            Err(_) => MicrophonePermissionStatus::Unknown,
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::MicrophonePermissionStatus;
+
+    /// Linux has no unified microphone-permission API (PulseAudio/ALSA/portal
+    /// setups vary too much to probe reliably), so we report `Unknown` and
+    /// let the caller assume access is granted until a device open fails.
+    pub fn check_status() -> MicrophonePermissionStatus {
+        MicrophonePermissionStatus::Unknown
+    }
+
+    pub async fn request() -> MicrophonePermissionStatus {
+        MicrophonePermissionStatus::Unknown
+    }
+}